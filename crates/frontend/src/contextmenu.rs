@@ -0,0 +1,21 @@
+use std::rc::Rc;
+
+use futures_signals::signal::Mutable;
+
+use crate::workspace::activity_panel::Activity;
+
+pub struct ContextMenuState {
+    pub show_menu: Mutable<bool>,
+    pub menu_position: Mutable<(i32, i32)>,
+    pub clicked_activity: Mutable<Option<Rc<Activity>>>,
+}
+
+impl ContextMenuState {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self {
+            show_menu: Mutable::new(false),
+            menu_position: Mutable::new((0, 0)),
+            clicked_activity: Mutable::new(None),
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use std::rc::Rc;
+
+use futures::channel::mpsc;
+
+pub mod contextmenu;
+pub mod workspace;
+
+pub type WorkspaceCommandSender = mpsc::UnboundedSender<WorkspaceCommand>;
+pub type WorkspaceCommandReceiver = mpsc::UnboundedReceiver<WorkspaceCommand>;
+
+pub enum WorkspaceCommand {
+    OpenFile(Rc<workspace::activity_panel::editor::File>),
+    OpenTerminal,
+    CloseTab,
+    CloseAllTabs,
+    SwitchToActivity(Rc<workspace::activity_panel::Activity>),
+}
+
+/// Yields to the browser's microtask queue once. Needed anywhere a status
+/// transition (e.g. `ActivityStatus::Working` then `Idle`) needs a real
+/// `await` point in between to be observable — two `Mutable::set` calls made
+/// back-to-back in the same synchronous stretch never let a render see the
+/// intermediate value.
+pub(crate) async fn yield_once() {
+    let promise = js_sys::Promise::resolve(&wasm_bindgen::JsValue::NULL);
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
@@ -0,0 +1,190 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, Dom};
+use dominator_bulma::{block, column};
+use futures_signals::{
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+
+use super::{editor, Activity, ActivityPanel};
+
+pub struct Command {
+    pub label: String,
+    pub build: Rc<dyn Fn() -> crate::WorkspaceCommand>,
+}
+
+pub struct Match {
+    pub command: Rc<Command>,
+    pub score: u32,
+    pub matched_indices: Vec<usize>,
+}
+
+pub struct CommandPaletteState {
+    pub query: Mutable<String>,
+    pub matches: MutableVec<Rc<Match>>,
+}
+
+impl CommandPaletteState {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self {
+            query: Mutable::new(String::new()),
+            matches: MutableVec::new(),
+        })
+    }
+}
+
+/// Subsequence fuzzy match of `query` against `candidate`. Returns `None` when
+/// `query` isn't a subsequence of `candidate`, otherwise a score (higher is
+/// better) and the matched char indices within `candidate` for highlighting.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(u32, Vec<usize>)> {
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+
+    let mut matched_indices = Vec::new();
+    let mut score: u32 = 0;
+    let mut search_from = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.chars() {
+        let index = candidate_chars[search_from..]
+            .iter()
+            .position(|candidate_char| candidate_char.eq_ignore_ascii_case(&query_char))
+            .map(|offset| search_from + offset)?;
+
+        score += 1;
+
+        if previous_match == Some(index.wrapping_sub(1)) {
+            score += 2;
+        }
+
+        let is_word_boundary = index == 0 || {
+            let previous_char = candidate_chars[index - 1];
+            previous_char == ' ' || previous_char == '_' || previous_char == '-'
+                || (candidate_chars[index].is_uppercase() && previous_char.is_lowercase())
+        };
+        if is_word_boundary {
+            score += 3;
+        }
+
+        matched_indices.push(index);
+        previous_match = Some(index);
+        search_from = index + 1;
+    }
+
+    Some((score, matched_indices))
+}
+
+fn commands_for(panel: &Rc<ActivityPanel>) -> Vec<Rc<Command>> {
+    let mut commands = vec![
+        Rc::new(Command {
+            label: "Open File".to_string(),
+            build: Rc::new(|| crate::WorkspaceCommand::OpenFile(Rc::new(editor::File::new(
+                "untitled".into(),
+                String::new(),
+            )))),
+        }),
+        Rc::new(Command {
+            label: "Close Tab".to_string(),
+            build: Rc::new(|| crate::WorkspaceCommand::CloseTab),
+        }),
+        Rc::new(Command {
+            label: "Close All Tabs".to_string(),
+            build: Rc::new(|| crate::WorkspaceCommand::CloseAllTabs),
+        }),
+        Rc::new(Command {
+            label: "New Terminal".to_string(),
+            build: Rc::new(|| crate::WorkspaceCommand::OpenTerminal),
+        }),
+    ];
+
+    for activity in panel.activities.lock_ref().iter() {
+        let activity = activity.clone();
+        let label = match activity.as_ref() {
+            Activity::Editor(editor) => format!("Go to: {}", editor.file.path.to_string_lossy()),
+            Activity::Terminal(_) => "Go to: Terminal".to_string(),
+            Activity::Welcome(_) => "Go to: Welcome".to_string(),
+        };
+
+        commands.push(Rc::new(Command {
+            label,
+            build: Rc::new(move || crate::WorkspaceCommand::SwitchToActivity(activity.clone())),
+        }));
+    }
+
+    commands
+}
+
+fn refresh_matches(panel: &Rc<ActivityPanel>, state: &Rc<CommandPaletteState>) {
+    let query = state.query.get_cloned();
+    let mut matches: Vec<_> = commands_for(panel)
+        .into_iter()
+        .filter_map(|command| {
+            let (score, matched_indices) = fuzzy_match(&query, &command.label)?;
+            (score > 0 || query.is_empty()).then(|| Rc::new(Match { command, score, matched_indices }))
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.command.label.len().cmp(&b.command.label.len())));
+
+    state.matches.lock_mut().replace_cloned(matches);
+}
+
+fn render_highlighted_label(label: &str, matched_indices: &[usize]) -> Dom {
+    html!("span", {
+        .children(label.chars().enumerate().map(|(index, character)| {
+            html!("span", {
+                .apply_if(matched_indices.contains(&index), |dom| dom.class("has-text-weight-bold"))
+                .text(&character.to_string())
+            })
+        }))
+    })
+}
+
+pub fn render(
+    panel: &Rc<ActivityPanel>,
+    state: &Rc<CommandPaletteState>,
+    overlay: &Rc<Mutable<Option<Rc<CommandPaletteState>>>>,
+) -> Dom {
+    refresh_matches(panel, state);
+
+    block!("p-4", {
+        .style("position", "absolute")
+        .style("top", "10%")
+        .style("left", "50%")
+        .style("transform", "translateX(-50%)")
+        .style("width", "480px")
+        .style("z-index", "1000")
+        .class("has-background-white")
+        .class("has-shadow")
+        .child(html!("input", {
+            .attr("type", "text")
+            .attr("placeholder", "Type a command...")
+            .prop_signal("value", state.query.signal_cloned())
+            .event(clone!(panel, state => move |event: events::Input| {
+                if let Some(value) = event.value() {
+                    state.query.set(value);
+                    refresh_matches(&panel, &state);
+                }
+            }))
+            .event(clone!(overlay => move |event: events::KeyDown| {
+                if event.key() == "Escape" {
+                    overlay.set(None);
+                }
+            }))
+        }))
+        .child(column!("is-full", {
+            .children_signal_vec(state.matches.signal_vec_cloned().map(clone!(panel, overlay => move |command_match| {
+                html!("div", {
+                    .style("cursor", "pointer")
+                    .child(render_highlighted_label(&command_match.command.label, &command_match.matched_indices))
+                    .event(clone!(panel, overlay, command_match => move |_: events::MouseDown| {
+                        overlay.set(None);
+                        wasm_bindgen_futures::spawn_local(clone!(panel, command_match => async move {
+                            ActivityPanel::apply_command(&panel, (command_match.command.build)()).await;
+                        }));
+                    }))
+                })
+            })))
+        }))
+    })
+}
@@ -0,0 +1,132 @@
+use std::{cell::RefCell, ops::Range};
+
+use tree_sitter::{Query, QueryCursor, Tree, WasmStore};
+
+/// A tree-sitter grammar plus its highlight query, compiled to wasm so it can
+/// run in the browser. One `Language` is shared by every open file of that
+/// kind; per-file parse state (the parser and its previous tree) lives on
+/// the `Editor` itself so reparses can stay incremental.
+pub struct Language {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+    pub grammar: tree_sitter::Language,
+    pub highlight_query: Query,
+}
+
+/// Grammars are compiled to wasm, so every parse needs the `WasmStore` that
+/// holds their compiled modules attached to the `Parser`. `Parser` can only
+/// borrow one `WasmStore` at a time, so callers must `take` it before
+/// parsing and hand it back via [`return_wasm_store`] afterwards.
+pub fn take_wasm_store() -> WasmStore {
+    REGISTRY.with(|registry| registry.wasm_store.borrow_mut().take().expect("wasm store already taken"))
+}
+
+pub fn return_wasm_store(wasm_store: WasmStore) {
+    REGISTRY.with(|registry| *registry.wasm_store.borrow_mut() = Some(wasm_store));
+}
+
+#[derive(Clone)]
+pub struct HighlightSpan {
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub capture_name: String,
+}
+
+struct GrammarSource {
+    name: &'static str,
+    extensions: &'static [&'static str],
+    wasm: &'static [u8],
+    highlights_query: &'static str,
+}
+
+// `build.rs` downloads each grammar's compiled wasm module and highlight
+// query into `OUT_DIR` before this crate compiles, so there's nothing to
+// vendor here; bumping a grammar means updating its pinned URLs and their
+// sha256 checksums together in `build.rs`.
+const GRAMMARS: &[GrammarSource] = &[
+    GrammarSource {
+        name: "rust",
+        extensions: &["rs"],
+        wasm: include_bytes!(concat!(env!("OUT_DIR"), "/tree-sitter-rust.wasm")),
+        highlights_query: include_str!(concat!(env!("OUT_DIR"), "/rust.scm")),
+    },
+    GrammarSource {
+        name: "toml",
+        extensions: &["toml"],
+        wasm: include_bytes!(concat!(env!("OUT_DIR"), "/tree-sitter-toml.wasm")),
+        highlights_query: include_str!(concat!(env!("OUT_DIR"), "/toml.scm")),
+    },
+    GrammarSource {
+        name: "typescript",
+        extensions: &["ts", "tsx"],
+        wasm: include_bytes!(concat!(env!("OUT_DIR"), "/tree-sitter-typescript.wasm")),
+        highlights_query: include_str!(concat!(env!("OUT_DIR"), "/typescript.scm")),
+    },
+    GrammarSource {
+        name: "yaml",
+        extensions: &["yaml", "yml"],
+        wasm: include_bytes!(concat!(env!("OUT_DIR"), "/tree-sitter-yaml.wasm")),
+        highlights_query: include_str!(concat!(env!("OUT_DIR"), "/yaml.scm")),
+    },
+];
+
+struct Registry {
+    languages: Vec<Language>,
+    wasm_store: RefCell<Option<WasmStore>>,
+}
+
+thread_local! {
+    static REGISTRY: Registry = load_registry();
+}
+
+fn load_registry() -> Registry {
+    let mut wasm_store = WasmStore::new(tree_sitter::wasmtime::Engine::default())
+        .expect("failed to create tree-sitter wasm store");
+
+    let languages = GRAMMARS.iter().filter_map(|source| {
+        let grammar = wasm_store.load_language(source.name, source.wasm).ok()?;
+        let highlight_query = Query::new(&grammar, source.highlights_query).ok()?;
+
+        Some(Language {
+            name: source.name,
+            extensions: source.extensions,
+            grammar,
+            highlight_query,
+        })
+    }).collect();
+
+    Registry { languages, wasm_store: RefCell::new(Some(wasm_store)) }
+}
+
+/// Looks up the grammar registered for a file extension (e.g. `"rs"`,
+/// `"toml"`) and hands it to `f`. Scoped rather than returned by reference
+/// since the registry lives in thread-local storage.
+pub fn with_language_for_extension<R>(extension: &str, f: impl FnOnce(Option<&Language>) -> R) -> R {
+    REGISTRY.with(|registry| {
+        f(registry.languages.iter().find(|language| language.extensions.contains(&extension)))
+    })
+}
+
+/// Runs the language's highlight query over a parsed tree, returning byte
+/// spans tagged with the capture name (e.g. `"keyword"`, `"string"`) so the
+/// editor can map them to CSS classes. `byte_range` restricts the query to
+/// the given region (e.g. the range tree-sitter reports as changed after an
+/// incremental edit) instead of re-matching the whole tree; pass `None` to
+/// query the full source, as is needed for a file's first parse.
+pub fn highlight(language: &Language, tree: &Tree, source: &str, byte_range: Option<Range<usize>>) -> Vec<HighlightSpan> {
+    let mut cursor = QueryCursor::new();
+    let source_bytes = source.as_bytes();
+
+    if let Some(byte_range) = byte_range {
+        cursor.set_byte_range(byte_range);
+    }
+
+    cursor.matches(&language.highlight_query, tree.root_node(), source_bytes)
+        .flat_map(|query_match| query_match.captures.to_vec())
+        .map(|capture| HighlightSpan {
+            start_byte: capture.node.start_byte(),
+            end_byte: capture.node.end_byte(),
+            capture_name: language.highlight_query.capture_names()[capture.index as usize].to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,362 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use dominator::{clone, events, html, Dom};
+use dominator_bulma::icon;
+use futures_signals::{
+    map_ref,
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+use tree_sitter::Parser;
+
+pub mod language;
+
+use language::HighlightSpan;
+use super::status::ActivityStatus;
+
+pub struct File {
+    pub path: PathBuf,
+    pub contents: Mutable<String>,
+}
+
+impl File {
+    pub fn new(path: PathBuf, contents: String) -> Self {
+        Self {
+            path,
+            contents: Mutable::new(contents),
+        }
+    }
+
+    fn extension(&self) -> String {
+        self.path.extension().map(|extension| extension.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+}
+
+pub struct Editor {
+    pub file: Rc<File>,
+    parser: RefCell<Parser>,
+    tree: RefCell<Option<tree_sitter::Tree>>,
+    /// Contents as of the last reparse, kept so the next reparse can diff
+    /// against it to recover the edited byte range for `Tree::edit`.
+    last_contents: RefCell<String>,
+    highlights: MutableVec<HighlightSpan>,
+    /// Shared with the rest of the panel so reparsing can report itself as
+    /// busy, the same status model `OpenFile`/`CloseTab` already push into.
+    status: Rc<Mutable<ActivityStatus>>,
+}
+
+impl Editor {
+    pub fn new(file: Rc<File>, status: Rc<Mutable<ActivityStatus>>) -> Self {
+        let editor = Self {
+            file,
+            parser: RefCell::new(Parser::new()),
+            tree: RefCell::new(None),
+            last_contents: RefCell::new(String::new()),
+            highlights: MutableVec::new(),
+            status,
+        };
+        // The initial parse's own Working/Idle pulse is subsumed by the
+        // OpenFile command's "Opening file..." status; only edits made after
+        // the editor exists get their own pulse, via reparse_with_status.
+        editor.reparse();
+        editor
+    }
+
+    /// Reparses the file's current contents. Before parsing, tells the
+    /// previous tree which byte range changed via `Tree::edit` so tree-sitter
+    /// only re-walks the edited region instead of reparsing from scratch, then
+    /// only requeries and patches the highlight spans tree-sitter says
+    /// actually changed instead of rescanning the whole file every keystroke.
+    fn reparse(&self) {
+        let extension = self.file.extension();
+        let contents = self.file.contents.get_cloned();
+
+        language::with_language_for_extension(&extension, |language| {
+            let Some(language) = language else {
+                self.highlights.lock_mut().clear();
+                *self.tree.borrow_mut() = None;
+                *self.last_contents.borrow_mut() = contents;
+                return;
+            };
+
+            let mut parser = self.parser.borrow_mut();
+
+            let wasm_store = language::take_wasm_store();
+            parser.set_wasm_store(wasm_store).expect("attach tree-sitter wasm store");
+            parser.set_language(&language.grammar).expect("load grammar into parser");
+
+            let edit = {
+                let last_contents = self.last_contents.borrow();
+                edit_for_change(&last_contents, &contents)
+            };
+
+            if let Some(edit) = edit {
+                if let Some(tree) = self.tree.borrow_mut().as_mut() {
+                    tree.edit(&edit);
+                }
+            }
+
+            let old_tree = self.tree.borrow().clone();
+            let new_tree = parser.parse(&contents, old_tree.as_ref());
+
+            language::return_wasm_store(parser.take_wasm_store().expect("reclaim tree-sitter wasm store"));
+
+            if let Some(new_tree) = new_tree {
+                match (&old_tree, edit) {
+                    (Some(old_tree), Some(edit)) => self.patch_highlights(language, old_tree, &new_tree, &edit, &contents),
+                    _ => {
+                        let spans = language::highlight(language, &new_tree, &contents, None);
+                        self.highlights.lock_mut().replace_cloned(spans);
+                    },
+                }
+                *self.tree.borrow_mut() = Some(new_tree);
+            }
+
+            *self.last_contents.borrow_mut() = contents;
+        });
+    }
+
+    /// Requeries only the byte range tree-sitter reports as changed (rather
+    /// than the whole file) and splices the result into `highlights` with
+    /// granular `MutableVec` mutations (shift/remove/insert), so both the
+    /// query cost and the update itself scale with the size of the edit, not
+    /// the size of the file.
+    fn patch_highlights(
+        &self,
+        language: &language::Language,
+        old_tree: &tree_sitter::Tree,
+        new_tree: &tree_sitter::Tree,
+        edit: &tree_sitter::InputEdit,
+        contents: &str,
+    ) {
+        let delta = edit.new_end_byte as isize - edit.old_end_byte as isize;
+
+        let changed_ranges: Vec<_> = old_tree.changed_ranges(new_tree).collect();
+
+        let mut highlights = self.highlights.lock_mut();
+
+        // Spans after the edit point shift by the length delta regardless of
+        // whether tree-sitter considers their region "changed" — byte
+        // offsets past an insertion/deletion always move.
+        for index in 0..highlights.len() {
+            if highlights[index].start_byte >= edit.old_end_byte {
+                let mut shifted = highlights[index].clone();
+                shifted.start_byte = (shifted.start_byte as isize + delta) as usize;
+                shifted.end_byte = (shifted.end_byte as isize + delta) as usize;
+                highlights.set_cloned(index, shifted);
+            }
+        }
+
+        let (Some(start), Some(end)) = (
+            changed_ranges.iter().map(|range| range.start_byte).min(),
+            changed_ranges.iter().map(|range| range.end_byte).max(),
+        ) else {
+            return;
+        };
+
+        highlights.retain(|span| span.end_byte <= start || span.start_byte >= end);
+
+        for span in language::highlight(language, new_tree, contents, Some(start..end)) {
+            let index = highlights.iter().position(|existing| existing.start_byte > span.start_byte).unwrap_or(highlights.len());
+            highlights.insert_cloned(index, span);
+        }
+    }
+
+    fn set_contents(this: &Rc<Editor>, contents: String) {
+        this.file.contents.set(contents);
+        wasm_bindgen_futures::spawn_local(clone!(this => async move {
+            Editor::reparse_with_status(&this).await;
+        }));
+    }
+
+    /// Reparses with a genuinely observable `Working`/`Idle` pulse: a real
+    /// `await` point between the two `status.set` calls (see `yield_once`)
+    /// is what lets a render actually see "Parsing..." before it flips back,
+    /// rather than the two sets coalescing within the same synchronous call.
+    async fn reparse_with_status(this: &Rc<Editor>) {
+        this.status.set(ActivityStatus::Working { message: "Parsing...".to_string(), progress: None });
+        crate::yield_once().await;
+        this.reparse();
+        this.status.set(ActivityStatus::Idle);
+    }
+
+    pub fn render(
+        this: &Rc<Editor>,
+        _width: impl Signal<Item = u32> + 'static,
+        _height: impl Signal<Item = u32> + 'static
+    ) -> impl Signal<Item = Option<Dom>> {
+        map_ref! {
+            let contents = this.file.contents.signal_cloned(),
+            let highlights = this.highlights.signal_vec_cloned().to_signal_cloned() =>
+            (contents.clone(), highlights.clone())
+        }.map(clone!(this => move |(contents, highlights)| {
+            Some(html!("pre", {
+                .class("is-family-monospace")
+                .attr("contenteditable", "true")
+                .style("outline", "none")
+                .style("white-space", "pre-wrap")
+                .children(render_spans(&contents, &highlights))
+                .event(clone!(this => move |event: events::Input| {
+                    if let Some(target) = event.dyn_target::<web_sys::HtmlElement>() {
+                        Editor::set_contents(&this, target.inner_text());
+                    }
+                }))
+            }))
+        }))
+    }
+
+    pub fn label(&self) -> Dom {
+        let name = self.file.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.file.path.to_string_lossy().into_owned());
+
+        html!("span", { .text(&name) })
+    }
+
+    pub fn icon(&self) -> Dom {
+        icon!({
+            .child(html!("span", { .text("📄") }))
+        })
+    }
+}
+
+/// Diffs `old` and `new` by common prefix/suffix to recover the byte range
+/// `Tree::edit` needs. We only ever see the full post-edit contents (the
+/// `contenteditable` `Input` event hands over `inner_text`, not a DOM
+/// range), so this is the best approximation available without also
+/// tracking cursor/selection state on every keystroke.
+fn edit_for_change(old: &str, new: &str) -> Option<tree_sitter::InputEdit> {
+    if old == new {
+        return None;
+    }
+
+    let old_bytes = old.as_bytes();
+    let new_bytes = new.as_bytes();
+
+    let common_prefix = old_bytes.iter().zip(new_bytes).take_while(|(a, b)| a == b).count();
+
+    let old_suffix_candidate = &old_bytes[common_prefix..];
+    let new_suffix_candidate = &new_bytes[common_prefix..];
+    let common_suffix = old_suffix_candidate.iter().rev()
+        .zip(new_suffix_candidate.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let start_byte = common_prefix;
+    let old_end_byte = old_bytes.len() - common_suffix;
+    let new_end_byte = new_bytes.len() - common_suffix;
+
+    Some(tree_sitter::InputEdit {
+        start_byte,
+        old_end_byte,
+        new_end_byte,
+        start_position: point_at(old, start_byte),
+        old_end_position: point_at(old, old_end_byte),
+        new_end_position: point_at(new, new_end_byte),
+    })
+}
+
+fn point_at(text: &str, byte_offset: usize) -> tree_sitter::Point {
+    let mut row = 0;
+    let mut column = 0;
+
+    for &byte in &text.as_bytes()[..byte_offset] {
+        if byte == b'\n' {
+            row += 1;
+            column = 0;
+        } else {
+            column += 1;
+        }
+    }
+
+    tree_sitter::Point { row, column }
+}
+
+/// Maps a tree-sitter capture name (e.g. `"keyword"`, `"string"`) to the
+/// Bulma/CSS color class used to render it.
+fn capture_class(capture_name: &str) -> Option<&'static str> {
+    match capture_name {
+        "keyword" | "keyword.control" => Some("has-text-link"),
+        "string" | "string.special" => Some("has-text-success"),
+        "comment" => Some("has-text-grey"),
+        "number" | "constant" | "constant.builtin" => Some("has-text-warning"),
+        "function" | "function.method" => Some("has-text-primary"),
+        "type" | "type.builtin" => Some("has-text-info"),
+        "variable.parameter" | "property" => Some("has-text-dark"),
+        _ => None,
+    }
+}
+
+/// Tree-sitter highlight queries routinely emit nested/overlapping captures
+/// (e.g. `string.special` inside a `string`, `function.method` inside a
+/// larger expression). Resolves overlaps innermost-first: at each byte, the
+/// smallest enclosing span's capture wins, instead of later captures
+/// silently vanishing wherever they happen to overlap an earlier one.
+fn resolve_overlaps(spans: &[HighlightSpan]) -> Vec<HighlightSpan> {
+    let mut boundaries: Vec<usize> = spans.iter().flat_map(|span| [span.start_byte, span.end_byte]).collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut resolved: Vec<HighlightSpan> = Vec::new();
+
+    for window in boundaries.windows(2) {
+        let (start, end) = (window[0], window[1]);
+
+        let winner = spans.iter()
+            .filter(|span| span.start_byte <= start && span.end_byte >= end)
+            .min_by_key(|span| span.end_byte - span.start_byte);
+
+        let Some(winner) = winner else { continue };
+
+        if let Some(last) = resolved.last_mut() {
+            if last.end_byte == start && last.capture_name == winner.capture_name {
+                last.end_byte = end;
+                continue;
+            }
+        }
+
+        resolved.push(HighlightSpan {
+            start_byte: start,
+            end_byte: end,
+            capture_name: winner.capture_name.clone(),
+        });
+    }
+
+    resolved
+}
+
+fn render_spans(contents: &str, highlights: &[HighlightSpan]) -> Vec<Dom> {
+    let spans = resolve_overlaps(highlights);
+
+    let mut nodes = Vec::new();
+    let mut cursor = 0;
+
+    for span in &spans {
+        if span.start_byte < cursor || span.start_byte >= contents.len() {
+            continue;
+        }
+
+        if span.start_byte > cursor {
+            nodes.push(html!("span", { .text(&contents[cursor..span.start_byte]) }));
+        }
+
+        let end_byte = span.end_byte.min(contents.len());
+        let text = &contents[span.start_byte..end_byte];
+
+        nodes.push(html!("span", {
+            .apply_if(capture_class(&span.capture_name).is_some(), |dom| {
+                dom.class(capture_class(&span.capture_name).unwrap())
+            })
+            .text(text)
+        }));
+
+        cursor = end_byte;
+    }
+
+    if cursor < contents.len() {
+        nodes.push(html!("span", { .text(&contents[cursor..]) }));
+    }
+
+    nodes
+}
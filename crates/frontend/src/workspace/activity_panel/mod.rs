@@ -3,16 +3,22 @@ use std::{pin::Pin, rc::Rc};
 use dominator::{clone, events, html, svg, Dom, EventOptions};
 use dominator_bulma::{block, column, columns, icon, icon_text};
 use futures::StreamExt;
-use futures_signals::{signal::{self, Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
+use futures_signals::{map_ref, signal::{self, Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
 use crate::contextmenu::ContextMenuState;
 
+mod command_palette;
 pub mod editor;
+pub mod status;
+pub mod terminal;
 pub mod welcome;
 
+use status::ActivityStatus;
+
 const TAB_HEIGHT: u32 = 48;
 
-enum Activity {
+pub(crate) enum Activity {
     Editor(Rc<editor::Editor>),
+    Terminal(Rc<terminal::Terminal>),
     Welcome(Rc<welcome::Welcome>),
 }
 
@@ -24,6 +30,7 @@ impl Activity {
     ) -> Pin<Box<dyn Signal<Item = Option<dominator::Dom>>>> {
         match this.as_ref() {
             Activity::Editor(editor) => Box::pin(editor::Editor::render(editor, width, height)),
+            Activity::Terminal(terminal) => Box::pin(terminal::Terminal::render(terminal, width, height)),
             Activity::Welcome(welcome) => Box::pin(welcome::Welcome::render(welcome, width, height)),
         }
     }
@@ -31,6 +38,7 @@ impl Activity {
     pub fn label(&self) -> Dom {
         match self {
             Activity::Editor(editor) => editor.label(),
+            Activity::Terminal(terminal) => terminal.label(),
             Activity::Welcome(welcome) => welcome.label(),
         }
     }
@@ -38,13 +46,15 @@ impl Activity {
     pub fn icon(&self) -> Dom {
         match self {
             Activity::Editor(editor) => editor.icon(),
+            Activity::Terminal(terminal) => terminal.icon(),
             Activity::Welcome(welcome) => welcome.icon(),
         }
     }
 
     fn render_tab(
         this: &Rc<Activity>,
-        panel: &Rc<ActivityPanel>
+        panel: &Rc<ActivityPanel>,
+        index: Mutable<Option<usize>>
     ) -> Dom {
         let close_icon = svg!("svg", {
             .attr("height", "1em")
@@ -62,23 +72,61 @@ impl Activity {
 
         block!("py-3", "px-3", {
             .style("cursor", "pointer")
+            .style("position", "relative")
+            .attr("draggable", "true")
+            .child_signal(map_ref! {
+                let drag_over_index = panel.drag_over_index.signal(),
+                let index = index.signal() =>
+                (*drag_over_index).is_some() && *drag_over_index == *index
+            }.map(|show_indicator| show_indicator.then(|| html!("div", {
+                .class("tab-drop-indicator")
+                .style("position", "absolute")
+                .style("left", "0")
+                .style("top", "0")
+                .style("bottom", "0")
+                .style("width", "2px")
+                .style("background-color", "hsl(217, 71%, 53%)")
+            }))))
+            .event(clone!(panel, index => move |_: events::DragStart| {
+                panel.dragged_index.set(index.get());
+            }))
+            .event_with_options(&EventOptions::preventable(), move |event: events::DragOver| {
+                event.prevent_default();
+            })
+            .event(clone!(panel, index => move |_: events::DragEnter| {
+                panel.drag_over_index.set_neq(index.get());
+            }))
+            .event_with_options(&EventOptions::preventable(), clone!(panel, index => move |event: events::Drop| {
+                event.prevent_default();
+                if let (Some(from), Some(to)) = (panel.dragged_index.get(), index.get()) {
+                    if from != to {
+                        panel.activities.lock_mut().move_from_to(from, to);
+                    }
+                }
+                panel.dragged_index.set(None);
+                panel.drag_over_index.set(None);
+            }))
+            .event(clone!(panel => move |_: events::DragEnd| {
+                panel.dragged_index.set(None);
+                panel.drag_over_index.set(None);
+            }))
             .event(clone!(mouse_over => move |_: events::PointerOver| {
                 mouse_over.set_neq(true);
             }))
             .event(clone!(mouse_over => move |_: events::PointerOut| {
                 mouse_over.set_neq(false);
             }))
-            .event(clone!(panel, this => move |_: events::PointerDown| {                
+            .event(clone!(panel, this => move |_: events::PointerDown| {
                 panel.active_activity.set(Some(this.clone()))
             }))
-            
+
             .class_signal("has-background-white", signal::or(is_active, mouse_over.signal()))
             .child(icon_text!({
                 .child(icon!({
                     .child(this.icon())
                 }))
                 .child(this.label())
-                .apply_if(matches!(**this, Activity::Editor(_)), |dom| {
+                .apply_if(matches!(**this, Activity::Editor(_) | Activity::Terminal(_)), |dom| {
                     dom.child(icon!({
                         .event(clone!(mouse_over_close => move |_: events::PointerOver| {
                             mouse_over_close.set_neq(true);
@@ -107,17 +155,25 @@ impl Activity {
 pub struct ActivityPanel {
     activities: MutableVec<Rc<Activity>>,
     active_activity: Mutable<Option<Rc<Activity>>>,
-    context_menu_state:Rc<ContextMenuState>
+    context_menu_state:Rc<ContextMenuState>,
+    command_palette: Rc<Mutable<Option<Rc<command_palette::CommandPaletteState>>>>,
+    dragged_index: Mutable<Option<usize>>,
+    drag_over_index: Mutable<Option<usize>>,
+    status: Rc<Mutable<ActivityStatus>>,
 }
 
 impl Default for ActivityPanel {
     fn default() -> Self {
         let welcome = Rc::new(Activity::Welcome(Rc::new(welcome::Welcome::new())));
-        
+
         Self {
             activities: vec![welcome.clone()].into(),
             active_activity: Some(welcome).into(),
-            context_menu_state: ContextMenuState::new()
+            context_menu_state: ContextMenuState::new(),
+            command_palette: Rc::new(Mutable::new(None)),
+            dragged_index: Mutable::new(None),
+            drag_over_index: Mutable::new(None),
+            status: Rc::new(Mutable::new(ActivityStatus::default())),
         }
     }
 }
@@ -125,6 +181,113 @@ impl Default for ActivityPanel {
 const CLOSE_ICON_PATH: &str = "M19,6.41L17.59,5L12,10.59L6.41,5L5,6.41L10.59,12L5,17.59L6.41,19L12,13.41L17.59,19L19,17.59L13.41,12L19,6.41Z";
 
 impl ActivityPanel {
+    fn fixup_active_activity(&self, removed: &[Rc<Activity>]) {
+        let mut active_activity = self.active_activity.lock_mut();
+        if active_activity.as_ref().is_some_and(|active| removed.iter().any(|activity| Rc::ptr_eq(activity, active))) {
+            *active_activity = self.activities.lock_ref().first().cloned();
+        }
+    }
+
+    fn close_activity(&self, activity: &Rc<Activity>) {
+        self.activities.lock_mut().retain(|other| !Rc::ptr_eq(other, activity));
+        self.fixup_active_activity(&[activity.clone()]);
+    }
+
+    fn close_other_activities(&self, activity: &Rc<Activity>) {
+        let removed: Vec<_> = self.activities.lock_ref().iter().filter(|other| !Rc::ptr_eq(other, activity)).cloned().collect();
+        self.activities.lock_mut().retain(|other| Rc::ptr_eq(other, activity));
+        self.fixup_active_activity(&removed);
+    }
+
+    fn close_activities_to_the_right(&self, activity: &Rc<Activity>) {
+        let index = self.activities.lock_ref().iter().position(|other| Rc::ptr_eq(other, activity));
+        let Some(index) = index else { return };
+        let mut removed = Vec::new();
+        let mut position = 0;
+        self.activities.lock_mut().retain(|other| {
+            let keep = position <= index;
+            if !keep {
+                removed.push(other.clone());
+            }
+            position += 1;
+            keep
+        });
+        self.fixup_active_activity(&removed);
+    }
+
+    fn close_all_activities(&self) {
+        let removed: Vec<_> = self.activities.lock_ref().iter().cloned().collect();
+        self.activities.lock_mut().clear();
+        self.fixup_active_activity(&removed);
+    }
+
+    async fn apply_command(this: &Rc<ActivityPanel>, command: crate::WorkspaceCommand) {
+        match command {
+            crate::WorkspaceCommand::OpenFile(file) => {
+                this.status.set(ActivityStatus::Working { message: "Opening file...".to_string(), progress: None });
+                crate::yield_once().await;
+
+                let mut activities = this.activities.lock_mut();
+                let editor = activities.iter()
+                    .find(|activity| match &***activity {
+                        Activity::Editor(editor) => Rc::ptr_eq(&editor.file, &file),
+                        _ => false,
+                    })
+                    .cloned()
+                    .unwrap_or_else(move || {
+                        let editor = Rc::new(Activity::Editor(Rc::new(editor::Editor::new(file, this.status.clone()))));
+                        activities.push_cloned(editor.clone());
+                        editor
+                    });
+
+                this.active_activity.set(Some(editor));
+                this.status.set(ActivityStatus::Idle);
+            },
+            crate::WorkspaceCommand::CloseTab => {
+                match this.active_activity.get_cloned() {
+                    Some(active) => this.close_activity(&active),
+                    None => this.status.set(ActivityStatus::Error { message: "No active tab to close".to_string() }),
+                }
+            },
+            crate::WorkspaceCommand::CloseAllTabs => {
+                this.close_all_activities();
+            },
+            crate::WorkspaceCommand::SwitchToActivity(activity) => {
+                this.active_activity.set(Some(activity));
+            },
+            crate::WorkspaceCommand::OpenTerminal => {
+                let terminal = Rc::new(Activity::Terminal(terminal::Terminal::new()));
+                this.activities.lock_mut().push_cloned(terminal.clone());
+                this.active_activity.set(Some(terminal));
+            },
+        }
+    }
+
+    fn render_context_menu_items(
+        this: &Rc<ActivityPanel>,
+        context_menu_state: &Rc<ContextMenuState>
+    ) -> Vec<Dom> {
+        let entries: [(&str, fn(&ActivityPanel, &Rc<Activity>)); 4] = [
+            ("Close", ActivityPanel::close_activity),
+            ("Close Others", ActivityPanel::close_other_activities),
+            ("Close Tabs to the Right", ActivityPanel::close_activities_to_the_right),
+            ("Close All", |panel, _activity| panel.close_all_activities()),
+        ];
+
+        entries.into_iter().map(|(label, action)| {
+            html!("div", {
+                .text(label)
+                .style("cursor", "pointer")
+                .event(clone!(this, context_menu_state => move |_event: events::MouseDown| {
+                    if let Some(activity) = context_menu_state.clicked_activity.get_cloned() {
+                        action(&this, &activity);
+                    }
+                    context_menu_state.show_menu.set_neq(false);
+                }))
+            })
+        }).collect()
+    }
+
     pub fn render(
         this: &Rc<ActivityPanel>,
         workspace_command_rx: crate::WorkspaceCommandReceiver,
@@ -135,26 +298,19 @@ impl ActivityPanel {
         let width = width.broadcast();
         let height = height.broadcast();
         let context_menu_state = this.context_menu_state.clone();
-        
+        let command_palette = this.command_palette.clone();
+
         columns!("is-gapless", "is-mobile", "is-multiline", {
-            .future(workspace_command_rx.for_each(clone!(this => move |command| clone!(this => async move {
-                match command {
-                    crate::WorkspaceCommand::OpenFile(file) => {
-                        let mut activities = this.activities.lock_mut();
-                        let editor = activities.iter()
-                            .find(|activity| match &***activity {
-                                Activity::Editor(editor) => Rc::ptr_eq(&editor.file, &file),
-                                _ => false,
-                            })
-                            .cloned()
-                            .unwrap_or_else(move || {
-                                let editor = Rc::new(Activity::Editor(Rc::new(editor::Editor::new(file))));
-                                activities.push_cloned(editor.clone());
-                                editor
-                            });
-                        this.active_activity.set(Some(editor));
-                    },
+            .global_event(clone!(context_menu_state => move |_: events::PointerDown| {
+                context_menu_state.show_menu.set_neq(false);
+            }))
+            .global_event(clone!(command_palette => move |event: events::KeyDown| {
+                if event.key() == "p" && event.ctrl_key() && event.shift_key() {
+                    command_palette.set(Some(command_palette::CommandPaletteState::new()));
                 }
+            }))
+            .future(workspace_command_rx.for_each(clone!(this => move |command| clone!(this => async move {
+                Self::apply_command(&this, command).await;
             }))))
             .child_signal(activity_count.signal().map(clone!(height => move |count| {
                 (count == 0).then(|| Self::render_background(height.signal()))
@@ -162,71 +318,61 @@ impl ActivityPanel {
             .child(column!("is-full", {
                 .class("has-background-white-ter")
                 .child(columns!("is-gapless", "is-mobile", {
-                    .children_signal_vec(this.activities.signal_vec_cloned().map(clone!(this => move |activity| {
+                    .children_signal_vec(this.activities.signal_vec_cloned().enumerate().map(clone!(this => move |(index, activity)| {
                         column!("is-narrow", {
-                            .child(Activity::render_tab(&activity, &this))
-                            .event_with_options(&EventOptions::preventable(), clone!(context_menu_state => move |event: events::ContextMenu| {
-                                event.prevent_default();  
-                                context_menu_state.show_menu.set(true); 
-                                context_menu_state.menu_position.set((event.x(), event.y())); 
+                            .child(Activity::render_tab(&activity, &this, index))
+                            .event_with_options(&EventOptions::preventable(), clone!(context_menu_state, activity => move |event: events::ContextMenu| {
+                                event.prevent_default();
+                                context_menu_state.clicked_activity.set(Some(activity.clone()));
+                                context_menu_state.show_menu.set(true);
+                                context_menu_state.menu_position.set((event.x(), event.y()));
                             }))
-                            .child_signal(context_menu_state.show_menu.signal_ref(clone!(context_menu_state => move |&show| {
-                                if show {
-                                    Some(html!("div", {
-                                        .class("context-menu")
-                                        .style("position", "absolute")
-                                        .style("background-color", "lightgray")
-                                        .style("border", "1px solid black")
-                                        .style("padding", "10px")
-                                        .style("z-index", "1000")
-                                        .style_signal("left", context_menu_state.menu_position.signal_ref(|(x, _y)| {
-                                            format!("{}px", x)
-                                        }))
-                                        .style_signal("top", context_menu_state.menu_position.signal_ref(|(_x, y)| {
-                                            format!("{}px", y)
-                                        }))
-                                        .children(&mut [
-                                            html!("div", {
-                                                .text("Option 1")
-                                                .style("cursor", "pointer")
-                                                .event(clone!(context_menu_state => move |_event: events::MouseDown| {
-                                                    web_sys::console::log_1(&"Option 1 clicked".into());
-                                                    context_menu_state.show_menu.set_neq(false); // Hide the menu after clicking
-                                                }))
-                                            }),
-                                            html!("div", {
-                                                .text("Option 2")
-                                                .style("cursor", "pointer")
-                                                .event(clone!(context_menu_state => move |_event: events::MouseDown| {
-                                                    web_sys::console::log_1(&"Option 2 clicked".into());
-                                                    context_menu_state.show_menu.set_neq(false); // Hide the menu after clicking
-                                                }))
-                                            }),
-                                            html!("div", {
-                                                .text("Option 3")
-                                                .style("cursor", "pointer")
-                                                .event(clone!(context_menu_state => move |_event: events::MouseDown| {
-                                                    web_sys::console::log_1(&"Option 3 clicked".into());
-                                                    context_menu_state.show_menu.set_neq(false); // Hide the menu after clicking
-                                                }))
-                                            }),
-                                            html!("div", {
-                                                .text("Option 4")
-                                                .style("cursor", "pointer")
-                                                .event(clone!(context_menu_state => move |_event: events::MouseDown| {
-                                                    web_sys::console::log_1(&"Option 4 clicked".into());
-                                                    context_menu_state.show_menu.set_neq(false); // Hide the menu after clicking
-                                                }))
-                                            })
-                                        ])
-                                    }))
-                                } else {
-                                    None
-                                }
-                            })))
                         })
                     })))
+                    .child(column!("is-narrow", {
+                        .child(block!("py-3", "px-3", {
+                            .style("cursor", "pointer")
+                            .attr("title", "Command Palette (Ctrl+Shift+P)")
+                            .child(icon_text!({
+                                .child(icon!({ .child(html!("span", { .text("⌘") })) }))
+                            }))
+                            .event(clone!(command_palette => move |_: events::PointerDown| {
+                                command_palette.set(Some(command_palette::CommandPaletteState::new()));
+                            }))
+                        }))
+                    }))
                 }))
+                // One shared instance for the whole panel, not per-tab: every
+                // tab sets clicked_activity/menu_position on the same
+                // context_menu_state, so rendering it per-tab produced one
+                // duplicate, identically-positioned menu per open tab.
+                .child_signal(context_menu_state.show_menu.signal_ref(clone!(context_menu_state, this => move |&show| {
+                    if show {
+                        Some(html!("div", {
+                            .class("context-menu")
+                            .style("position", "absolute")
+                            .style("background-color", "lightgray")
+                            .style("border", "1px solid black")
+                            .style("padding", "10px")
+                            .style("z-index", "1000")
+                            .style_signal("left", context_menu_state.menu_position.signal_ref(|(x, _y)| {
+                                format!("{}px", x)
+                            }))
+                            .style_signal("top", context_menu_state.menu_position.signal_ref(|(_x, y)| {
+                                format!("{}px", y)
+                            }))
+                            .event_with_options(&EventOptions::preventable(), |event: events::PointerDown| {
+                                event.stop_propagation();
+                            })
+                            .children(&mut Self::render_context_menu_items(&this, &context_menu_state))
+                        }))
+                    } else {
+                        None
+                    }
+                })))
+            }))
+            .child(column!("is-full", {
+                .child(status::render(&this.status))
             }))
             .child_signal(this.active_activity
                 .signal_cloned()
@@ -239,6 +385,9 @@ impl ActivityPanel {
                     })))
                 )
             )
+            .child_signal(command_palette.signal_cloned().map(clone!(this, command_palette => move |state| {
+                state.map(|state| command_palette::render(&this, &state, &command_palette))
+            })))
         })
     }
 
@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, Dom};
+use dominator_bulma::{icon, icon_text};
+use futures_signals::signal::{Mutable, SignalExt};
+
+#[derive(Clone)]
+pub enum ActivityStatus {
+    Idle,
+    Working { message: String, progress: Option<f32> },
+    Error { message: String },
+}
+
+impl Default for ActivityStatus {
+    fn default() -> Self {
+        ActivityStatus::Idle
+    }
+}
+
+pub fn render(status: &Rc<Mutable<ActivityStatus>>) -> Dom {
+    html!("div", {
+        .class("py-2")
+        .class("px-3")
+        .child_signal(status.signal_cloned().map(clone!(status => move |current| {
+            Some(match current {
+                ActivityStatus::Idle => icon_text!({
+                    .child(icon!({ .child(html!("span", { .text("●") })) }))
+                    .child(html!("span", { .text("Ready") }))
+                }),
+                ActivityStatus::Working { message, progress } => icon_text!({
+                    .child(icon!({
+                        .class("is-spinning")
+                        .child(html!("span", { .text("◐") }))
+                    }))
+                    .child(html!("span", { .text(&message) }))
+                    .apply_if(progress.is_some(), |dom| {
+                        dom.child(html!("progress", {
+                            .class("progress")
+                            .attr("max", "1")
+                            .attr("value", &progress.unwrap().to_string())
+                        }))
+                    })
+                }),
+                ActivityStatus::Error { message } => icon_text!({
+                    .style("cursor", "pointer")
+                    .class("has-text-danger")
+                    .child(icon!({ .child(html!("span", { .text("⚠") })) }))
+                    .child(html!("span", { .text(&message) }))
+                    .event(clone!(status => move |_: events::PointerDown| {
+                        status.set(ActivityStatus::Idle);
+                    }))
+                }),
+            })
+        })))
+    })
+}
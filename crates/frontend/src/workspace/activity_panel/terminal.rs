@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, Dom};
+use dominator_bulma::{block, icon};
+use futures::{channel::mpsc, StreamExt};
+use futures_signals::{
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+
+pub struct Terminal {
+    pub output: MutableVec<String>,
+    pub input: Mutable<String>,
+    command_tx: mpsc::UnboundedSender<String>,
+}
+
+impl Terminal {
+    /// Nothing in this WASM testbed can actually run a command yet, so
+    /// submitted lines are dispatched through a channel to a task that
+    /// stands in for a real shell by echoing them back as output. Swapping
+    /// in a real backend later only means changing what drains `command_rx`.
+    pub fn new() -> Rc<Self> {
+        let (command_tx, command_rx) = mpsc::unbounded();
+
+        let this = Rc::new(Self {
+            output: MutableVec::new(),
+            input: Mutable::new(String::new()),
+            command_tx,
+        });
+
+        wasm_bindgen_futures::spawn_local(clone!(this => async move {
+            let mut command_rx = command_rx;
+            while let Some(line) = command_rx.next().await {
+                this.output.lock_mut().push_cloned(format!("$ {line}"));
+            }
+        }));
+
+        this
+    }
+
+    fn submit_line(this: &Rc<Terminal>) {
+        let line = this.input.replace(String::new());
+        if !line.is_empty() {
+            let _ = this.command_tx.unbounded_send(line);
+        }
+    }
+
+    pub fn render(
+        this: &Rc<Terminal>,
+        _width: impl Signal<Item = u32> + 'static,
+        height: impl Signal<Item = u32> + 'static
+    ) -> impl Signal<Item = Option<Dom>> {
+        height.map(clone!(this => move |height| {
+            Some(block!("p-3", {
+                .class("is-family-monospace")
+                .style("height", format!("{height}px"))
+                .style("overflow-y", "auto")
+                .style("background-color", "black")
+                .style("color", "whitesmoke")
+                .children_signal_vec(this.output.signal_vec_cloned().map(|line| {
+                    html!("div", { .text(&line) })
+                }))
+                .child(html!("div", {
+                    .style("display", "flex")
+                    .child(html!("span", { .text("$ ") }))
+                    .child(html!("input", {
+                        .class("is-family-monospace")
+                        .style("background", "transparent")
+                        .style("color", "inherit")
+                        .style("border", "none")
+                        .style("outline", "none")
+                        .style("flex", "1")
+                        .prop_signal("value", this.input.signal_cloned())
+                        .event(clone!(this => move |event: events::Input| {
+                            if let Some(value) = event.value() {
+                                this.input.set(value);
+                            }
+                        }))
+                        .event(clone!(this => move |event: events::KeyDown| {
+                            if event.key() == "Enter" {
+                                Terminal::submit_line(&this);
+                            }
+                        }))
+                    }))
+                }))
+            }))
+        }))
+    }
+
+    pub fn label(&self) -> Dom {
+        html!("span", { .text("Terminal") })
+    }
+
+    pub fn icon(&self) -> Dom {
+        icon!({
+            .child(html!("span", { .text(">_") }))
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use std::rc::Rc;
+
+use dominator::{html, Dom};
+use dominator_bulma::icon;
+use futures_signals::signal::{Signal, SignalExt};
+
+pub struct Welcome {}
+
+impl Welcome {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn render(
+        _this: &Rc<Welcome>,
+        width: impl Signal<Item = u32> + 'static,
+        height: impl Signal<Item = u32> + 'static
+    ) -> impl Signal<Item = Option<Dom>> {
+        width.map(move |width| width).with_latest_from(height).map(|(width, height)| {
+            Some(html!("div", {
+                .style("width", format!("{width}px"))
+                .style("height", format!("{height}px"))
+                .child(html!("h1", { .text("Welcome") }))
+            }))
+        })
+    }
+
+    pub fn label(&self) -> Dom {
+        html!("span", { .text("Welcome") })
+    }
+
+    pub fn icon(&self) -> Dom {
+        icon!({
+            .child(html!("span", { .text("🏠") }))
+        })
+    }
+}